@@ -8,7 +8,10 @@ use view::terminal::Terminal;
 pub struct ScrollableRegion {
     terminal: Arc<Terminal>,
     line_offset: usize,
+    column_offset: usize,
     wrapped_line_count: usize,
+    line_count: usize,
+    scroll_off: usize,
 }
 
 #[derive(PartialEq, Debug)]
@@ -23,7 +26,10 @@ impl ScrollableRegion {
         ScrollableRegion {
             terminal: terminal,
             line_offset: 0,
+            column_offset: 0,
             wrapped_line_count: 0,
+            line_count: 0,
+            scroll_off: 0,
         }
     }
     // Determines the visible lines based on the current line offset and height.
@@ -34,18 +40,44 @@ impl ScrollableRegion {
     /// If necessary, moves the line offset such that the specified line is
     /// visible, using previous state to determine whether said line is at
     /// the top or bottom of the new visible range.
+    /// When a scroll-off margin is set, the line is instead kept that many
+    /// lines away from the edge (also when it is already visible but inside
+    /// the margin), except near the start or end of the buffer where the
+    /// offset is clamped.
     pub fn scroll_into_view(&mut self, line: usize) {
+        let scroll_off = self.effective_scroll_off();
         let range = self.visible_range();
-        if line < range.start() {
-            self.line_offset = line;
-        } else if line >= range.end() {
-            self.line_offset = line - self.height() + 1;
+        if line < range.start() + scroll_off {
+            self.line_offset = line.saturating_sub(scroll_off);
+        } else if line >= range.end().saturating_sub(scroll_off) {
+            self.line_offset = (line + scroll_off + 1).saturating_sub(self.height());
         }
+        self.clamp_line_offset();
     }
 
     /// Moves the line offset such that the specified line is centered vertically.
     pub fn scroll_to_center(&mut self, line: usize) {
         self.line_offset = line.checked_sub(self.height() / 2).unwrap_or(0);
+        self.clamp_line_offset();
+    }
+
+    /// Determines the visible columns based on the current column offset
+    /// and the full terminal width. Used when soft-wrap is off to decide
+    /// which cells of a long line fall within the viewport.
+    pub fn visible_columns(&self) -> LineRange {
+        LineRange::new(self.column_offset, self.terminal.width() + self.column_offset)
+    }
+
+    /// If necessary, moves the column offset such that the specified column
+    /// is visible, keeping it flush against the left or right edge of the
+    /// region, clamped so the offset never goes below column 0.
+    pub fn column_into_view(&mut self, column: usize) {
+        let range = self.visible_columns();
+        if column < range.start() {
+            self.column_offset = column;
+        } else if column >= range.end() {
+            self.column_offset = column - self.terminal.width() + 1;
+        }
     }
 
     /// Converts an absolutely positioned line number into
@@ -65,12 +97,34 @@ impl ScrollableRegion {
         }
     }
 
+    /// Converts an absolutely positioned column number into one relative to
+    /// the scrollable region's visible columns. The visibility type is based
+    /// on whether or not the column is outside of the region's visible range.
+    pub fn relative_column_position(&self, column: usize) -> Visibility {
+        match column.checked_sub(self.column_offset) {
+            Some(column) => {
+                if column >= self.terminal.width() {
+                    Visibility::BelowRegion
+                } else {
+                    Visibility::Visible(column)
+                }
+            }
+            None => Visibility::AboveRegion,
+        }
+    }
+
     /// The number of lines the region has scrolled over.
     /// A value of zero represents an unscrolled region.
     pub fn line_offset(&self) -> usize {
         self.line_offset
     }
 
+    /// The number of columns the region has scrolled over.
+    /// A value of zero represents a horizontally unscrolled region.
+    pub fn column_offset(&self) -> usize {
+        self.column_offset
+    }
+
     pub fn scroll_up(&mut self, amount: usize) {
         self.line_offset = match self.line_offset.checked_sub(amount) {
             Some(amount) => amount,
@@ -80,6 +134,75 @@ impl ScrollableRegion {
 
     pub fn scroll_down(&mut self, amount: usize) {
         self.line_offset += amount;
+        self.clamp_line_offset();
+    }
+
+    /// Caps the line offset so that the last line of the buffer can never
+    /// scroll above the bottom of the region; the region pins to the bottom
+    /// instead of running off into blank space. A line count of zero means
+    /// the buffer size is unknown, in which case no bound is applied.
+    fn clamp_line_offset(&mut self) {
+        if self.line_count > 0 {
+            let max_offset = self.line_count.saturating_sub(self.height());
+            if self.line_offset > max_offset {
+                self.line_offset = max_offset;
+            }
+        }
+    }
+
+    /// Scrolls up by a full page, moving the cursor with the viewport.
+    /// Returns the number of lines the viewport actually moved, which is
+    /// `min(height(), line_offset)`: near the top of the buffer the
+    /// viewport moves less than a full page, so the caller lands on the
+    /// topmost line rather than scrolling past it.
+    pub fn scroll_page_up(&mut self) -> usize {
+        self.scroll_up_by(self.height())
+    }
+
+    /// Scrolls down by a full page, moving the cursor with the viewport.
+    /// Returns the number of lines the viewport actually moved, which may
+    /// be less than a full page when clamped against the end of the buffer.
+    pub fn scroll_page_down(&mut self) -> usize {
+        self.scroll_down_by(self.height())
+    }
+
+    /// Scrolls up by half a page, moving the cursor with the viewport.
+    /// Returns the number of lines the viewport actually moved.
+    pub fn scroll_half_page_up(&mut self) -> usize {
+        self.scroll_up_by(self.height() / 2)
+    }
+
+    /// Scrolls down by half a page, moving the cursor with the viewport.
+    /// Returns the number of lines the viewport actually moved.
+    pub fn scroll_half_page_down(&mut self) -> usize {
+        self.scroll_down_by(self.height() / 2)
+    }
+
+    /// Scrolls up by the requested amount, returning the distance the
+    /// line offset actually moved so the caller can shift the cursor to match.
+    fn scroll_up_by(&mut self, amount: usize) -> usize {
+        let previous_offset = self.line_offset;
+        self.scroll_up(amount);
+        previous_offset - self.line_offset
+    }
+
+    /// Scrolls down by the requested amount, returning the distance the
+    /// line offset actually moved so the caller can shift the cursor to match.
+    fn scroll_down_by(&mut self, amount: usize) -> usize {
+        let previous_offset = self.line_offset;
+        self.scroll_down(amount);
+        self.line_offset - previous_offset
+    }
+
+    pub fn scroll_left(&mut self, amount: usize) {
+        self.column_offset = match self.column_offset.checked_sub(amount) {
+            Some(amount) => amount,
+            None => 0,
+        };
+    }
+
+    pub fn scroll_right(&mut self, amount: usize) {
+        self.column_offset += amount;
     }
 
     /// Scrollable regions occupy one line short of the full
@@ -94,6 +217,24 @@ impl ScrollableRegion {
     pub fn set_wrapped_line_count(&mut self, count: usize) {
         self.wrapped_line_count = count
     }
+
+    /// Records the total number of lines in the buffer, which is used to
+    /// keep the line offset from scrolling past the end of the document.
+    pub fn set_line_count(&mut self, count: usize) {
+        self.line_count = count
+    }
+
+    /// Sets the number of context lines kept visible above and below the
+    /// cursor when scrolling it into view (Vim's `scrolloff`).
+    pub fn set_scroll_off(&mut self, lines: usize) {
+        self.scroll_off = lines
+    }
+
+    /// The scroll-off margin clamped to at most half the region's height,
+    /// so it degrades gracefully in short terminals.
+    fn effective_scroll_off(&self) -> usize {
+        self.scroll_off.min(self.height() / 2)
+    }
 }
 
 #[cfg(test)]
@@ -213,6 +354,195 @@ mod tests {
         assert_eq!(region.visible_range(), LineRange::new(0, 9));
     }
 
+    #[test]
+    fn scroll_page_down_moves_viewport_by_a_full_page_and_returns_delta() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        let delta = region.scroll_page_down();
+        assert_eq!(delta, region.height());
+        assert_eq!(region.line_offset(), region.height());
+    }
+
+    #[test]
+    fn scroll_page_up_returns_only_the_distance_actually_scrolled() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_down(3);
+        let delta = region.scroll_page_up();
+        assert_eq!(delta, 3);
+        assert_eq!(region.line_offset(), 0);
+    }
+
+    #[test]
+    fn scroll_half_page_down_moves_viewport_by_half_a_page() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        let expected = region.height() / 2;
+        let delta = region.scroll_half_page_down();
+        assert_eq!(delta, expected);
+        assert_eq!(region.line_offset(), expected);
+    }
+
+    #[test]
+    fn scroll_half_page_up_returns_only_the_distance_actually_scrolled() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_down(2);
+        let delta = region.scroll_half_page_up();
+        assert_eq!(delta, 2);
+        assert_eq!(region.line_offset(), 0);
+    }
+
+    #[test]
+    fn scroll_down_clamps_line_offset_to_the_end_of_the_buffer() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_line_count(20);
+        region.scroll_down(100);
+        assert_eq!(region.line_offset(), 20 - region.height());
+    }
+
+    #[test]
+    fn scroll_down_is_unbounded_when_line_count_is_unset() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_down(100);
+        assert_eq!(region.line_offset(), 100);
+    }
+
+    #[test]
+    fn scroll_into_view_clamps_line_offset_to_the_end_of_the_buffer() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_line_count(20);
+        region.scroll_into_view(19);
+        assert_eq!(region.line_offset(), 20 - region.height());
+    }
+
+    #[test]
+    fn scroll_to_center_clamps_line_offset_to_the_end_of_the_buffer() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_line_count(20);
+        region.scroll_to_center(19);
+        assert_eq!(region.line_offset(), 20 - region.height());
+    }
+
+    #[test]
+    fn scroll_into_view_keeps_scroll_off_context_below_the_cursor() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_scroll_off(3);
+        region.scroll_into_view(8);
+        assert_eq!(region.line_offset(), 3);
+    }
+
+    #[test]
+    fn scroll_into_view_keeps_scroll_off_context_above_the_cursor() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_scroll_off(3);
+        region.scroll_down(20);
+        region.scroll_into_view(21);
+        assert_eq!(region.line_offset(), 18);
+    }
+
+    #[test]
+    fn scroll_into_view_with_scroll_off_never_produces_negative_offset() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_scroll_off(3);
+        region.scroll_into_view(1);
+        assert_eq!(region.line_offset(), 0);
+    }
+
+    #[test]
+    fn scroll_off_is_clamped_to_half_the_region_height() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.set_scroll_off(100);
+        region.scroll_into_view(8);
+        assert_eq!(region.line_offset(), 4);
+    }
+
+    #[test]
+    fn visible_columns_works_for_zero_based_column_offsets() {
+        let terminal = Arc::new(TestTerminal::new());
+        let region = ScrollableRegion::new(terminal.clone());
+        assert_eq!(
+            region.visible_columns(),
+            LineRange::new(0, terminal.width())
+        );
+    }
+
+    #[test]
+    fn scroll_right_increases_column_offset_by_amount() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_right(5);
+        assert_eq!(region.column_offset(), 5);
+    }
+
+    #[test]
+    fn scroll_left_decreases_column_offset_by_amount() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_right(5);
+        region.scroll_left(3);
+        assert_eq!(region.column_offset(), 2);
+    }
+
+    #[test]
+    fn scroll_left_does_not_scroll_beyond_left_edge_of_region() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_left(5);
+        assert_eq!(region.column_offset(), 0);
+    }
+
+    #[test]
+    fn column_into_view_advances_region_if_column_after_current_range() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal.clone());
+        region.column_into_view(40);
+        assert_eq!(region.column_offset(), 40 - terminal.width() + 1);
+    }
+
+    #[test]
+    fn column_into_view_recedes_region_if_column_before_current_range() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_right(10);
+        region.column_into_view(5);
+        assert_eq!(region.column_offset(), 5);
+    }
+
+    #[test]
+    fn relative_column_position_returns_correct_value_when_positive() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_right(10);
+        assert_eq!(region.relative_column_position(13), Visibility::Visible(3));
+    }
+
+    #[test]
+    fn relative_column_position_returns_above_region_when_negative() {
+        let terminal = Arc::new(TestTerminal::new());
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_right(10);
+        assert_eq!(region.relative_column_position(0), Visibility::AboveRegion);
+    }
+
+    #[test]
+    fn relative_column_position_returns_below_region_when_beyond_visible_range() {
+        let terminal = Arc::new(TestTerminal::new());
+        let region = ScrollableRegion::new(terminal.clone());
+        assert_eq!(
+            region.relative_column_position(terminal.width()),
+            Visibility::BelowRegion
+        );
+    }
+
     #[test]
     fn height_is_always_at_least_one_less_than_terminal_height() {
         let terminal = Arc::new(TestTerminal::new());